@@ -1,20 +1,153 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use colored::Colorize;
+use glob::Pattern;
+use ignore::WalkBuilder;
+use log::{LevelFilter, Log, Metadata, Record};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::thread;
 use std::time::Instant;
-use walkdir::WalkDir;
 
-fn is_allowed_path(path: &Path) -> bool {
-    if let Some(s) = path.to_str() {
-        return s.contains("src/utils/logging") || s.ends_with("src/utils/logging.rs");
+/// Rule id reported for the built-in forbidden-logging pattern.
+const RULE_ID: &str = "logging-check/forbidden-log-macro";
+
+/// Severity attached to a rule; controls the process exit code and how a match
+/// is rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Severity {
+    #[default]
+    Error,
+    Warning,
+}
+
+impl Severity {
+    fn label(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
     }
-    false
 }
 
+/// One forbidden-API rule as written in `logging-check.toml`.
+#[derive(Debug, Deserialize)]
+struct RawRule {
+    pattern: String,
+    description: String,
+    #[serde(default)]
+    severity: Severity,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    allowed_paths: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawConfig {
+    #[serde(default)]
+    rules: Vec<RawRule>,
+}
+
+/// A compiled rule: its regex and allowed-path globs are built once up front and
+/// shared across the worker pool.
 #[derive(Debug)]
+struct Rule {
+    id: String,
+    regex: Regex,
+    description: String,
+    severity: Severity,
+    allowed: Vec<Pattern>,
+}
+
+impl Rule {
+    /// Whether `path` is an allowed location for this rule and so exempt from it.
+    fn is_allowed(&self, path: &Path) -> bool {
+        self.allowed.iter().any(|g| g.matches_path(path))
+    }
+}
+
+/// Compile a user-supplied path glob. The walk yields absolute paths, so a
+/// relative glob (the documented form, e.g. `src/utils/logging/**`) is anchored
+/// with a leading `**/` — the same treatment the built-in rules apply — so it
+/// matches regardless of where the repository lives on disk.
+fn compile_glob(glob: &str) -> Result<Pattern> {
+    let normalized = if glob.starts_with('/') || glob.starts_with("**") {
+        glob.to_string()
+    } else {
+        format!("**/{glob}")
+    };
+    Pattern::new(&normalized).with_context(|| format!("invalid glob {glob:?}"))
+}
+
+/// The built-in rule set applied when no `logging-check.toml` is present: forbid
+/// `log::{info|warn|debug|trace}` everywhere except `src/utils/logging`.
+fn default_rules() -> Result<Vec<Rule>> {
+    Ok(vec![Rule {
+        id: RULE_ID.to_string(),
+        regex: Regex::new(r"\blog::(info|warn|debug|trace)\b")?,
+        description: "Direct log::{info|warn|debug|trace} usage outside src/utils/logging"
+            .to_string(),
+        severity: Severity::Error,
+        allowed: vec![
+            Pattern::new("**/src/utils/logging.rs").unwrap(),
+            Pattern::new("**/src/utils/logging").unwrap(),
+            Pattern::new("**/src/utils/logging/**").unwrap(),
+        ],
+    }])
+}
+
+/// Load `logging-check.toml` from `repo_root`, compiling each rule, or fall back
+/// to [`default_rules`] when no config file exists.
+fn load_rules(repo_root: &Path) -> Result<Vec<Rule>> {
+    let path = repo_root.join("logging-check.toml");
+    if !path.exists() {
+        return default_rules();
+    }
+
+    let text = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let config: RawConfig =
+        toml::from_str(&text).with_context(|| format!("failed to parse {}", path.display()))?;
+
+    if config.rules.is_empty() {
+        return default_rules();
+    }
+
+    config
+        .rules
+        .into_iter()
+        .enumerate()
+        .map(|(idx, raw)| {
+            let regex = Regex::new(&raw.pattern)
+                .with_context(|| format!("invalid regex for rule {}", idx + 1))?;
+            let allowed = raw
+                .allowed_paths
+                .iter()
+                .map(|g| {
+                    compile_glob(g).with_context(|| format!("invalid glob in rule {}", idx + 1))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Rule {
+                id: raw.id.unwrap_or_else(|| format!("rule-{}", idx + 1)),
+                regex,
+                description: raw.description,
+                severity: raw.severity,
+                allowed,
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Serialize)]
 struct Violation {
+    rule_id: String,
+    severity: Severity,
     file: PathBuf,
     line_no: usize,
     col_start: usize,
@@ -22,6 +155,78 @@ struct Violation {
     line_text: String,
 }
 
+/// How the report is rendered. `Human` is the colorized text shown by default;
+/// `Json`/`Sarif` emit a single machine-readable document for CI consumption.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Human,
+    Json,
+    Sarif,
+}
+
+impl OutputFormat {
+    fn parse(s: &str) -> Result<Self> {
+        match s {
+            "human" => Ok(OutputFormat::Human),
+            "json" => Ok(OutputFormat::Json),
+            "sarif" => Ok(OutputFormat::Sarif),
+            other => bail!("unknown --format {other:?} (expected human, json, or sarif)"),
+        }
+    }
+}
+
+fn print_json(violations: &[Violation]) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(&violations)?);
+    Ok(())
+}
+
+/// Emit a minimal SARIF 2.1.0 document so GitHub code-scanning can annotate PRs.
+fn print_sarif(violations: &[Violation]) -> Result<()> {
+    let results: Vec<serde_json::Value> = violations
+        .iter()
+        .map(|v| {
+            serde_json::json!({
+                "ruleId": v.rule_id,
+                "level": v.severity.label(),
+                "message": { "text": "Forbidden API usage" },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": v.file.display().to_string() },
+                        "region": {
+                            "startLine": v.line_no,
+                            "startColumn": v.col_start + 1,
+                            "endColumn": v.col_end + 1,
+                            "snippet": { "text": v.line_text }
+                        }
+                    }
+                }]
+            })
+        })
+        .collect();
+
+    let mut rule_ids: Vec<&str> = violations.iter().map(|v| v.rule_id.as_str()).collect();
+    rule_ids.sort_unstable();
+    rule_ids.dedup();
+    let rules: Vec<serde_json::Value> =
+        rule_ids.iter().map(|id| serde_json::json!({ "id": id })).collect();
+
+    let doc = serde_json::json!({
+        "version": "2.1.0",
+        "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "logging-check",
+                    "rules": rules
+                }
+            },
+            "results": results
+        }]
+    });
+    println!("{}", serde_json::to_string_pretty(&doc)?);
+    Ok(())
+}
+
 fn highlight_match(line: &str, col_start: usize, col_end: usize) -> String {
     let before = &line[..col_start];
     let matched = &line[col_start..col_end];
@@ -33,39 +238,183 @@ fn calc_col_in_line(_line: &str, byte_index_in_file: usize, line_start_in_file:
     byte_index_in_file.saturating_sub(line_start_in_file)
 }
 
-fn main() -> Result<()> {
-    let start = Instant::now();
-    let repo_root = std::env::current_dir()?;
-    let re = Regex::new(r"\blog::(info|warn|debug|trace)\b")?;
-
-    let mut violations: Vec<Violation> = Vec::new();
-    let mut files_scanned: usize = 0usize;
+/// A rotating log sink: timestamped lines are appended to a file and the file is
+/// rolled over to `<path>.1` once it exceeds `max_bytes`.
+struct RotatingFile {
+    path: PathBuf,
+    max_bytes: u64,
+    handle: std::fs::File,
+    written: u64,
+}
 
-    for entry in WalkDir::new(&repo_root)
-        .into_iter()
-        .filter_entry(|e| {
-            let name = e.file_name().to_string_lossy();
-            !(name == "target" || name == ".git" || name == "node_modules")
+impl RotatingFile {
+    fn open(path: PathBuf, max_bytes: u64) -> Result<Self> {
+        let handle = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("failed to open log file {}", path.display()))?;
+        let written = handle.metadata().map(|m| m.len()).unwrap_or(0);
+        Ok(Self {
+            path,
+            max_bytes,
+            handle,
+            written,
         })
-        .filter_map(|e| e.ok())
-        .filter(|e| {
-            e.file_type().is_file() && e.path().extension().map(|ext| ext == "rs").unwrap_or(false)
-        })
-    {
-        let path = entry.path().to_path_buf();
-        files_scanned += 1;
+    }
 
-        if is_allowed_path(&path) {
-            continue;
+    fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        if self.written + line.len() as u64 + 1 > self.max_bytes {
+            let rotated = {
+                let mut s = self.path.clone().into_os_string();
+                s.push(".1");
+                PathBuf::from(s)
+            };
+            let _ = std::fs::rename(&self.path, rotated);
+            self.handle = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?;
+            self.written = 0;
+        }
+        writeln!(self.handle, "{line}")?;
+        self.written += line.len() as u64 + 1;
+        Ok(())
+    }
+}
+
+/// A logger that mirrors diagnostics to stderr — filtered by the `-q`/`-v`
+/// console level — and, when `--log-file` is set, to a rotating file at full
+/// verbosity, modeled on rust-analyzer's custom file logger.
+struct CheckLogger {
+    console_level: LevelFilter,
+    file: Option<Mutex<RotatingFile>>,
+}
+
+impl Log for CheckLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        let ts = humantime::format_rfc3339_millis(std::time::SystemTime::now());
+        let line = format!("[{} {:>5}] {}", ts, record.level(), record.args());
+
+        if record.level() <= self.console_level {
+            eprintln!("{line}");
+        }
+        if let Some(file) = &self.file {
+            if let Ok(mut file) = file.lock() {
+                let _ = file.write_line(&line);
+            }
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(file) = &self.file {
+            if let Ok(mut file) = file.lock() {
+                let _ = file.handle.flush();
+            }
+        }
+    }
+}
+
+/// Install the global logger. `verbosity` is a signed offset from the default
+/// console level (`Warn`): `-q` lowers it, each `-v` raises it.
+fn init_logger(verbosity: i32, log_file: Option<PathBuf>) -> Result<()> {
+    let console_level = match verbosity {
+        i if i <= -1 => LevelFilter::Error,
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Info,
+        2 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    };
+
+    let file = match log_file {
+        Some(path) => Some(Mutex::new(RotatingFile::open(path, 5 * 1024 * 1024)?)),
+        None => None,
+    };
+
+    let logger = CheckLogger {
+        console_level,
+        file,
+    };
+    log::set_boxed_logger(Box::new(logger)).context("failed to install logger")?;
+    // The file sink wants every record; the console level is enforced per-record.
+    log::set_max_level(LevelFilter::Trace);
+    Ok(())
+}
+
+const ALLOW_NEXT_LINE: &str = "logging-check-allow-next-line";
+const ALLOW_START: &str = "logging-check-allow-start";
+const ALLOW_END: &str = "logging-check-allow-end";
+
+/// Precompute the 1-based line ranges fenced off by `// logging-check-allow-start`
+/// … `// logging-check-allow-end` markers. A start without a matching end suppresses
+/// through the end of the file.
+fn allow_spans(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut open: Option<usize> = None;
+    for (idx, line) in text.lines().enumerate() {
+        let line_no = idx + 1;
+        if line.contains(ALLOW_START) {
+            if open.is_none() {
+                open = Some(line_no);
+            }
+        } else if line.contains(ALLOW_END) {
+            if let Some(start) = open.take() {
+                spans.push((start, line_no));
+            }
         }
+    }
+    if let Some(start) = open {
+        spans.push((start, usize::MAX));
+    }
+    spans
+}
 
-        let text = fs::read_to_string(&path)
-            .with_context(|| format!("failed to read file {}", path.display()))?;
+/// Whether a match on `line_no` is whitelisted, either by a `logging-check-allow-next-line`
+/// marker on the closest preceding non-blank line or by falling inside an allow fence.
+fn is_allowed_line(lines: &[&str], line_no: usize, spans: &[(usize, usize)]) -> bool {
+    if spans.iter().any(|&(s, e)| line_no >= s && line_no <= e) {
+        return true;
+    }
+    lines[..line_no.saturating_sub(1)]
+        .iter()
+        .rev()
+        .find(|l| !l.trim().is_empty())
+        .map(|l| l.contains(ALLOW_NEXT_LINE))
+        .unwrap_or(false)
+}
 
-        for mat in re.find_iter(&text) {
+/// Read one file and return every match of every rule in it, honoring each rule's
+/// allowed paths and the inline allow markers. Returns an empty vec when no rule
+/// applies, so callers can dispatch this uniformly across a worker pool.
+fn scan_file(path: &Path, rules: &[Rule]) -> Result<Vec<Violation>> {
+    let applicable: Vec<&Rule> = rules.iter().filter(|r| !r.is_allowed(path)).collect();
+    if applicable.is_empty() {
+        // logging-check-allow-next-line
+        log::trace!("skipping {} (allowed by all rules)", path.display());
+        return Ok(Vec::new());
+    }
+
+    let file_start = Instant::now();
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("failed to read file {}", path.display()))?;
+
+    let spans = allow_spans(&text);
+    let lines: Vec<&str> = text.lines().collect();
+    let mut out = Vec::new();
+
+    for rule in applicable {
+        for mat in rule.regex.find_iter(&text) {
             let before = &text[..mat.start()];
             let line_no = before.matches('\n').count() + 1;
 
+            if is_allowed_line(&lines, line_no, &spans) {
+                continue;
+            }
+
             let last_newline_pos = before.rfind('\n').map(|p| p + 1).unwrap_or(0);
             let line_start_index = last_newline_pos;
             let line_end_index = text[line_start_index..]
@@ -77,8 +426,10 @@ fn main() -> Result<()> {
             let col_start = calc_col_in_line(&line_text, mat.start(), line_start_index);
             let col_end = calc_col_in_line(&line_text, mat.end(), line_start_index);
 
-            violations.push(Violation {
-                file: path.clone(),
+            out.push(Violation {
+                rule_id: rule.id.clone(),
+                severity: rule.severity,
+                file: path.to_path_buf(),
                 line_no,
                 col_start,
                 col_end,
@@ -87,7 +438,125 @@ fn main() -> Result<()> {
         }
     }
 
+    // logging-check-allow-next-line
+    log::debug!(
+        "scanned {} in {:.2?} ({} match(es))",
+        path.display(),
+        file_start.elapsed(),
+        out.len()
+    );
+    Ok(out)
+}
+
+/// Scan `paths` across a bounded pool of worker threads, merging the per-file
+/// `Vec<Violation>` fragments. The merged result is sorted by
+/// `(file, line_no, col_start)` so parallelism never perturbs report ordering.
+fn scan_parallel(paths: Vec<PathBuf>, rules: &[Rule]) -> Result<Vec<Violation>> {
+    let workers = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    let workers = workers.min(paths.len().max(1));
+
+    let queue = Arc::new(Mutex::new(paths.into_iter()));
+    let (tx, rx) = mpsc::channel::<Result<Vec<Violation>>>();
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let queue = Arc::clone(&queue);
+            let tx = tx.clone();
+            scope.spawn(move || loop {
+                let next = queue.lock().unwrap().next();
+                match next {
+                    Some(path) => {
+                        if tx.send(scan_file(&path, rules)).is_err() {
+                            break;
+                        }
+                    }
+                    None => break,
+                }
+            });
+        }
+        drop(tx);
+
+        let mut violations = Vec::new();
+        for fragment in rx {
+            violations.extend(fragment?);
+        }
+
+        violations.sort_by(|a, b| {
+            a.file
+                .cmp(&b.file)
+                .then(a.line_no.cmp(&b.line_no))
+                .then(a.col_start.cmp(&b.col_start))
+                .then(a.rule_id.cmp(&b.rule_id))
+        });
+        Ok(violations)
+    })
+}
+
+/// Directory names excluded from the walk (and from watch events).
+fn is_ignored_dir(name: &str) -> bool {
+    name == "target" || name == ".git" || name == "node_modules"
+}
+
+/// Collect every candidate `.rs` path under `repo_root` using the `ignore` crate's
+/// walker, which honors `.gitignore`, `.ignore`, and nested ignore files. The
+/// hardcoded directories are still filtered so behavior is unchanged on repos that
+/// do not ignore them, and `include`/`exclude` globs scope the run further.
+fn collect_candidates(repo_root: &Path, include: &[Pattern], exclude: &[Pattern]) -> Vec<PathBuf> {
+    WalkBuilder::new(repo_root)
+        .hidden(false)
+        .filter_entry(|e| {
+            let keep = !is_ignored_dir(&e.file_name().to_string_lossy());
+            if !keep {
+                // logging-check-allow-next-line
+                log::trace!("skipping ignored path {}", e.path().display());
+            }
+            keep
+        })
+        .build()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().map(|ft| ft.is_file()).unwrap_or(false))
+        .map(|e| e.into_path())
+        .filter(|p| p.extension().map(|ext| ext == "rs").unwrap_or(false))
+        .filter(|p| include.is_empty() || include.iter().any(|g| g.matches_path(p)))
+        .filter(|p| !exclude.iter().any(|g| g.matches_path(p)))
+        .collect()
+}
+
+/// Number of error- and warning-severity violations in a slice.
+fn severity_counts(violations: &[Violation]) -> (usize, usize) {
+    let errors = violations
+        .iter()
+        .filter(|v| v.severity == Severity::Error)
+        .count();
+    (errors, violations.len() - errors)
+}
+
+/// Scan `candidates`, print the report in the requested format, and return the
+/// violations found. Never exits the process, so it is reusable from both the
+/// one-shot path and the watch loop.
+fn run_once(candidates: Vec<PathBuf>, rules: &[Rule], format: OutputFormat) -> Result<Vec<Violation>> {
+    let start = Instant::now();
+    let files_scanned = candidates.len();
+    // logging-check-allow-start
+    log::info!("scanning {files_scanned} rust file(s)");
+    let violations = scan_parallel(candidates, rules)?;
+
     let total_violations = violations.len();
+    log::info!(
+        "found {total_violations} violation(s) in {:.2?}",
+        start.elapsed()
+    );
+    // logging-check-allow-end
+
+    if format != OutputFormat::Human {
+        match format {
+            OutputFormat::Json => print_json(&violations)?,
+            OutputFormat::Sarif => print_sarif(&violations)?,
+            OutputFormat::Human => unreachable!(),
+        }
+        return Ok(violations);
+    }
+
     let mut per_file_count = std::collections::BTreeMap::<PathBuf, usize>::new();
     for v in &violations {
         *per_file_count.entry(v.file.clone()).or_default() += 1;
@@ -105,18 +574,42 @@ fn main() -> Result<()> {
             "{}",
             "No forbidden log::{info|warn|debug|trace} usages found.".green()
         );
-        return Ok(());
+        return Ok(violations);
     }
 
+    let (error_count, warning_count) = severity_counts(&violations);
     println!(
         "{} {}",
         "Found".red().bold(),
-        format!("{} forbidden logging usage(s)", total_violations)
+        format!("{error_count} error(s), {warning_count} warning(s)")
             .red()
             .bold()
     );
     println!();
 
+    let mut per_rule_count = std::collections::BTreeMap::<String, usize>::new();
+    for v in &violations {
+        *per_rule_count.entry(v.rule_id.clone()).or_default() += 1;
+    }
+    let descriptions: std::collections::BTreeMap<&str, &Rule> =
+        rules.iter().map(|r| (r.id.as_str(), r)).collect();
+
+    println!("{}", "Summary by rule:".bold().underline());
+    for (rule_id, count) in &per_rule_count {
+        let (severity, description) = descriptions
+            .get(rule_id.as_str())
+            .map(|r| (r.severity.label(), r.description.as_str()))
+            .unwrap_or(("error", ""));
+        println!(
+            "  {}  [{}] {} — {}",
+            format!("{count:>3}x").yellow(),
+            severity,
+            rule_id.cyan(),
+            description
+        );
+    }
+    println!();
+
     println!("{}", "Summary by file:".bold().underline());
     for (file, count) in &per_file_count {
         println!(
@@ -128,41 +621,241 @@ fn main() -> Result<()> {
     println!();
 
     println!("{}", "Details:".bold().underline());
-    for (file, vcount) in &per_file_count {
-        println!("{} {}", "File:".cyan().bold(), file.display());
-        println!("  {} violations", vcount);
-        for v in violations.iter().filter(|vv| &vv.file == file) {
-            let highlighted = highlight_match(&v.line_text, v.col_start, v.col_end);
-            println!(
-                "    {}:{}: {}",
-                file.display(),
-                v.line_no.to_string().yellow(),
-                highlighted
-            );
-            if v.line_text.len() > 200 {
-                println!("      {}", "...(line truncated)".dimmed());
+    for (rule_id, rcount) in &per_rule_count {
+        println!("{} {}", "Rule:".cyan().bold(), rule_id);
+        println!("  {} violations", rcount);
+        for (file, _) in &per_file_count {
+            for v in violations
+                .iter()
+                .filter(|vv| &vv.file == file && &vv.rule_id == rule_id)
+            {
+                let highlighted = highlight_match(&v.line_text, v.col_start, v.col_end);
+                println!(
+                    "    {}:{}: {}",
+                    file.display(),
+                    v.line_no.to_string().yellow(),
+                    highlighted
+                );
+                if v.line_text.len() > 200 {
+                    println!("      {}", "...(line truncated)".dimmed());
+                }
             }
         }
         println!();
     }
 
     println!("{}", "Guidance:".bold().underline());
-    println!(
-        "  - Allowed location: {}",
-        "src/utils/logging".green().bold()
-    );
+    for rule in rules {
+        if rule.allowed.is_empty() {
+            continue;
+        }
+        let allowed: Vec<String> = rule.allowed.iter().map(|g| g.as_str().to_string()).collect();
+        println!(
+            "  - {}: allowed under {}",
+            rule.id.cyan(),
+            allowed.join(", ").green().bold()
+        );
+    }
     println!("  - Suggested fixes:");
-    println!("    * Move logging calls to the allowed module.");
+    println!("    * Move the flagged calls to an allowed module.");
     println!(
-        "    * Use other facilities (e.g. return values, events) instead of direct log calls where appropriate."
+        "    * Use other facilities (e.g. return values, events) instead of the forbidden API where appropriate."
     );
     println!();
 
-    eprintln!(
-        "{} {} violations in {} files. See details above.",
-        "ERROR:".red().bold(),
-        total_violations,
-        per_file_count.len()
-    );
-    std::process::exit(1);
+    if error_count > 0 {
+        eprintln!(
+            "{} {} error(s) ({} warning(s)) in {} files. See details above.",
+            "ERROR:".red().bold(),
+            error_count,
+            warning_count,
+            per_file_count.len()
+        );
+    } else {
+        eprintln!(
+            "{} {} warning(s) in {} files. See details above.",
+            "WARNING:".yellow().bold(),
+            warning_count,
+            per_file_count.len()
+        );
+    }
+
+    Ok(violations)
+}
+
+/// Whether a changed path is one we care to re-scan: a `.rs` file that does not
+/// live under one of the ignored directories.
+fn is_watched_rs(path: &Path) -> bool {
+    if path.extension().map(|ext| ext != "rs").unwrap_or(true) {
+        return false;
+    }
+    !path
+        .components()
+        .any(|c| is_ignored_dir(&c.as_os_str().to_string_lossy()))
+}
+
+/// Watch `repo_root` and re-scan changed `.rs` files as they are edited. Bursts of
+/// filesystem events are debounced before each re-scan; the process stays resident
+/// until interrupted, so the final exit code is never reached in practice.
+fn watch_loop(
+    repo_root: &Path,
+    rules: &[Rule],
+    format: OutputFormat,
+    initial: Vec<Violation>,
+) -> Result<()> {
+    use notify::{RecursiveMode, Watcher};
+    use std::sync::atomic::{AtomicI32, Ordering};
+    use std::time::Duration;
+
+    // Only error-severity violations fail the run; warnings are advisory.
+    let code_for = |errors: usize| if errors > 0 { 1 } else { 0 };
+
+    // Cumulative repo state keyed by file, so the exit code reflects violations in
+    // files untouched by the current burst, not just the rescanned subset.
+    let mut state = std::collections::HashMap::<PathBuf, Vec<Violation>>::new();
+    for v in initial {
+        state.entry(v.file.clone()).or_default().push(v);
+    }
+    let repo_errors =
+        |state: &std::collections::HashMap<PathBuf, Vec<Violation>>| -> usize {
+            state
+                .values()
+                .flatten()
+                .filter(|v| v.severity == Severity::Error)
+                .count()
+        };
+
+    // The process stays resident until interrupted, so the exit code is delivered
+    // from a Ctrl-C handler that reads the latest scan result rather than from a
+    // fallthrough `return` that is only reached if the watcher channel closes.
+    let exit_code = Arc::new(AtomicI32::new(code_for(repo_errors(&state))));
+    let handler_code = Arc::clone(&exit_code);
+    ctrlc::set_handler(move || std::process::exit(handler_code.load(Ordering::SeqCst)))
+        .context("failed to install Ctrl-C handler")?;
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |res| {
+        let _ = tx.send(res);
+    })
+    .context("failed to create file watcher")?;
+    watcher
+        .watch(repo_root, RecursiveMode::Recursive)
+        .with_context(|| format!("failed to watch {}", repo_root.display()))?;
+
+    println!("{}", "Watching for changes (Ctrl-C to stop)...".dimmed());
+
+    loop {
+        // Block for the first event, then drain the rest of the burst.
+        let first = match rx.recv() {
+            Ok(ev) => ev,
+            Err(_) => break,
+        };
+        let mut changed = std::collections::BTreeSet::<PathBuf>::new();
+        collect_changed(first, &mut changed);
+        while let Ok(ev) = rx.recv_timeout(Duration::from_millis(200)) {
+            collect_changed(ev, &mut changed);
+        }
+
+        if changed.is_empty() {
+            continue;
+        }
+
+        println!();
+        println!(
+            "{} {} file(s) changed, re-scanning...",
+            "==>".cyan().bold(),
+            changed.len()
+        );
+        // Drop prior results for every changed path (covering files that became
+        // clean or were deleted), then fold in the fresh scan grouped by file.
+        let changed: Vec<PathBuf> = changed.into_iter().collect();
+        for path in &changed {
+            state.remove(path);
+        }
+        // A delete/rename (branch switch, atomic editor save, rm) emits events for
+        // paths that no longer exist; scanning them would error and kill the loop,
+        // so only rescan paths still present on disk.
+        let present: Vec<PathBuf> = changed.into_iter().filter(|p| p.exists()).collect();
+        let fresh = run_once(present, rules, format)?;
+        for v in fresh {
+            state.entry(v.file.clone()).or_default().push(v);
+        }
+        exit_code.store(code_for(repo_errors(&state)), Ordering::SeqCst);
+    }
+
+    std::process::exit(exit_code.load(Ordering::SeqCst));
+}
+
+/// Fold a single watch event into `changed`, keeping only watched `.rs` paths.
+fn collect_changed(
+    event: notify::Result<notify::Event>,
+    changed: &mut std::collections::BTreeSet<PathBuf>,
+) {
+    if let Ok(event) = event {
+        for path in event.paths {
+            if is_watched_rs(&path) {
+                changed.insert(path);
+            }
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    let repo_root = std::env::current_dir()?;
+    let rules = load_rules(&repo_root)?;
+
+    let mut format = OutputFormat::Human;
+    let mut watch = false;
+    let mut include: Vec<Pattern> = Vec::new();
+    let mut exclude: Vec<Pattern> = Vec::new();
+    let mut log_file: Option<PathBuf> = None;
+    let mut verbosity: i32 = 0;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--log-file" => {
+                let value = args.next().context("--log-file requires a path")?;
+                log_file = Some(PathBuf::from(value));
+            }
+            "-q" | "--quiet" => verbosity -= 1,
+            "-v" | "--verbose" => verbosity += 1,
+            "-vv" => verbosity += 2,
+            "--format" => {
+                let value = args.next().context("--format requires a value")?;
+                format = OutputFormat::parse(&value)?;
+            }
+            other if other.starts_with("--format=") => {
+                format = OutputFormat::parse(&other["--format=".len()..])?;
+            }
+            "--include" => {
+                let value = args.next().context("--include requires a glob")?;
+                include.push(compile_glob(&value).context("invalid --include glob")?);
+            }
+            "--exclude" => {
+                let value = args.next().context("--exclude requires a glob")?;
+                exclude.push(compile_glob(&value).context("invalid --exclude glob")?);
+            }
+            "--watch" => watch = true,
+            other => bail!("unknown argument {other:?}"),
+        }
+    }
+
+    init_logger(verbosity, log_file)?;
+
+    let violations = run_once(
+        collect_candidates(&repo_root, &include, &exclude),
+        &rules,
+        format,
+    )?;
+
+    if !watch {
+        // Only error-severity violations fail the run; warnings are advisory.
+        let (errors, _) = severity_counts(&violations);
+        if errors > 0 {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    watch_loop(&repo_root, &rules, format, violations)
 }